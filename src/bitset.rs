@@ -0,0 +1,73 @@
+/// A packed bitset over node IDs, backed by a `Vec<u64>`. Used to represent
+/// each transaction's reachable-descendant set without the overhead of a
+/// `HashSet<usize>` per node.
+#[derive(Clone, Debug)]
+pub struct BitVector {
+    words: Vec<u64>,
+}
+
+impl BitVector {
+    /// Creates an empty bitset sized to hold IDs up to `capacity` (exclusive).
+    pub fn with_capacity(capacity: usize) -> Self {
+        BitVector {
+            words: vec![0u64; capacity / 64 + 1],
+        }
+    }
+
+    /// Sets bit `id`, growing the backing storage if necessary.
+    pub fn insert(&mut self, id: usize) {
+        let word = id / 64;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1u64 << (id % 64);
+    }
+
+    /// Returns whether bit `id` is set.
+    pub fn contains(&self, id: usize) -> bool {
+        let word = id / 64;
+        word < self.words.len() && self.words[word] & (1u64 << (id % 64)) != 0
+    }
+
+    /// OR-merges `other` into `self`, returning whether any bit changed.
+    pub fn insert_all(&mut self, other: &BitVector) -> bool {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+
+        let mut changed = false;
+        for (word, &other_word) in self.words.iter_mut().zip(other.words.iter()) {
+            let merged = *word | other_word;
+            if merged != *word {
+                changed = true;
+                *word = merged;
+            }
+        }
+
+        changed
+    }
+
+    /// Number of bits set, i.e. the cumulative weight of the node this
+    /// bitset belongs to.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_reflects_inserts_across_word_boundaries() {
+        let mut set = BitVector::with_capacity(8);
+        set.insert(3);
+        set.insert(70); // beyond the initial word, forces a resize
+
+        assert!(set.contains(3));
+        assert!(set.contains(70));
+        assert!(!set.contains(4));
+        assert!(!set.contains(71));
+        assert_eq!(set.count_ones(), 2);
+    }
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use crate::bitset::BitVector;
+use crate::graph::Graph;
+
+/// Computes, for every transaction, the full set of descendants that
+/// (transitively) approve it -- the "cumulative weight" central to tangle
+/// ledgers -- and returns the popcount of each node's set (1-indexed, index
+/// 0 unused).
+///
+/// Processes nodes in reverse topological order (children before parents)
+/// so each node's descendant set is computed exactly once, as the union of
+/// its children's sets, instead of recursing per node with caching only
+/// above a fan-out threshold -- that scheme recomputed the same low-fan-out
+/// subtrees repeatedly and blew up exponentially on realistic DAGs. A
+/// computed set is kept in `cache` only until every parent that needs it
+/// has consumed it (tracked via `remaining_uses`), then dropped, bounding
+/// memory without recomputing shared subtrees. `order` must be a valid
+/// topological order of `graph`, e.g. from `Graph::topological_order`.
+pub fn cumulative_weights(graph: &Graph, order: &[usize]) -> Vec<usize> {
+    let num_nodes = graph.node_count();
+    let mut remaining_uses = vec![0usize; num_nodes + 1];
+    for id in graph.nodes() {
+        remaining_uses[id] = graph.parents(id).len();
+    }
+
+    let mut cache: HashMap<usize, BitVector> = HashMap::new();
+    let mut weights = vec![0usize; num_nodes + 1];
+
+    for &node in order.iter().rev() {
+        let mut set = BitVector::with_capacity(num_nodes + 1);
+        for &child in graph.children(node) {
+            set.insert(child);
+            if let Some(child_set) = cache.get(&child) {
+                set.insert_all(child_set);
+            }
+
+            remaining_uses[child] -= 1;
+            if remaining_uses[child] == 0 {
+                cache.remove(&child);
+            }
+        }
+
+        weights[node] = set.count_ones();
+        if remaining_uses[node] > 0 {
+            cache.insert(node, set);
+        }
+    }
+
+    weights
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use crate::graph::Graph;
+
+/// Per-node topological depth plus the aggregate statistics derived from it.
+pub struct DepthReport {
+    /// `depth[v]` for each node, 1-indexed (index 0 unused).
+    pub depths: Vec<usize>,
+    pub average_depth: f64,
+    pub max_depth: usize,
+    /// Number of transactions at each computed depth.
+    pub histogram: HashMap<usize, usize>,
+}
+
+impl DepthReport {
+    /// Average number of transactions per depth level, excluding depth 0
+    /// (the genesis nodes), matching the metric previously reported from
+    /// BFS layers.
+    pub fn average_txs_per_depth_excluding_zero(&self) -> f64 {
+        let non_zero_levels: usize = self.histogram.keys().filter(|&&depth| depth != 0).count();
+        if non_zero_levels == 0 {
+            return 0.0;
+        }
+
+        let total: usize = self
+            .histogram
+            .iter()
+            .filter(|&(&depth, _)| depth != 0)
+            .map(|(_, count)| count)
+            .sum();
+
+        total as f64 / non_zero_levels as f64
+    }
+}
+
+/// Computes each node's depth via longest-path topological ordering:
+/// `depth[v] = 0` for genesis nodes (no parents) and
+/// `depth[v] = 1 + max(depth[left], depth[right])` otherwise. Unlike a BFS
+/// layer count, this correctly handles nodes reachable by parent paths of
+/// different lengths. `order` must be a valid topological order of `graph`
+/// (parents before children), e.g. from `Graph::topological_order`.
+pub fn compute_depths(graph: &Graph, order: &[usize]) -> DepthReport {
+    let num_nodes = graph.node_count();
+    let mut depth = vec![0usize; num_nodes + 1];
+
+    for &node in order {
+        for &child in graph.children(node) {
+            if depth[node] + 1 > depth[child] {
+                depth[child] = depth[node] + 1;
+            }
+        }
+    }
+
+    let mut histogram: HashMap<usize, usize> = HashMap::new();
+    for &node_depth in depth.iter().skip(1) {
+        *histogram.entry(node_depth).or_insert(0) += 1;
+    }
+
+    let total_depth: usize = depth.iter().skip(1).sum();
+    let average_depth = if num_nodes > 0 {
+        total_depth as f64 / num_nodes as f64
+    } else {
+        0.0
+    };
+    let max_depth = depth.iter().skip(1).copied().max().unwrap_or(0);
+
+    DepthReport {
+        depths: depth,
+        average_depth,
+        max_depth,
+        histogram,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::TransactionNode;
+
+    fn node(left: usize, right: usize) -> TransactionNode {
+        TransactionNode {
+            left,
+            right,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn cycle_is_rejected_instead_of_computing_depths() {
+        // Node 1 -> Node 2 -> Node 1.
+        let nodes = vec![node(2, 0), node(1, 0)];
+        let graph = Graph::build(&nodes).unwrap();
+        assert!(graph.topological_order().is_err());
+    }
+
+    #[test]
+    fn depth_takes_the_longest_parent_path() {
+        // 1 (depth 0) -> 2 (depth 1) -> 3, and 1 -> 3 directly: 3 must take
+        // the longer path through 2, not the shorter direct one.
+        let nodes = vec![node(0, 0), node(1, 0), node(1, 2)];
+        let graph = Graph::build(&nodes).unwrap();
+        let order = graph.topological_order().unwrap();
+        let report = compute_depths(&graph, &order);
+
+        assert_eq!(report.depths[1], 0);
+        assert_eq!(report.depths[2], 1);
+        assert_eq!(report.depths[3], 2);
+        assert_eq!(report.max_depth, 2);
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{self, Write};
+
+use crate::graph::Graph;
+use crate::TransactionNode;
+
+/// Writes the graph built by `Graph::build` out as a Graphviz DOT file so it
+/// can be piped to `dot -Tsvg` for visualization. Nodes are labeled with
+/// their ID and timestamp and grouped into `rank=same` subgraphs by `depths`
+/// (one entry per node, 1-indexed, as computed by `depth::compute_depths`).
+/// When `focus` is set, only the subgraph reachable from that node is
+/// emitted; `focus` must be a valid node ID or this returns an error.
+pub fn export_dot(
+    nodes: &[TransactionNode],
+    graph: &Graph,
+    out_path: &str,
+    focus: Option<usize>,
+    depths: &[usize],
+) -> io::Result<()> {
+    if let Some(start) = focus {
+        if !graph.contains(start) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "--dot-from {} is out of range (1..={})",
+                    start,
+                    graph.node_count()
+                ),
+            ));
+        }
+    }
+
+    let included = match focus {
+        Some(start) => flood_from(graph, start),
+        None => (1..=nodes.len()).collect(),
+    };
+
+    let mut file = File::create(out_path)?;
+    writeln!(file, "digraph ledger {{")?;
+    writeln!(file, "    rankdir=LR;")?;
+
+    for (node_index, node) in nodes.iter().enumerate() {
+        let id = node_index + 1;
+        if !included.contains(&id) {
+            continue;
+        }
+        writeln!(
+            file,
+            "    {} [label=\"#{} t={}\"];",
+            id, id, node.timestamp
+        )?;
+    }
+
+    let mut by_depth: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &id in &included {
+        let depth = depths.get(id).copied().unwrap_or(0);
+        by_depth.entry(depth).or_default().push(id);
+    }
+    let mut depth_levels: Vec<&usize> = by_depth.keys().collect();
+    depth_levels.sort();
+    for depth in depth_levels {
+        let ids = &by_depth[depth];
+        writeln!(file, "    {{ rank=same;")?;
+        for id in ids {
+            writeln!(file, "        {};", id)?;
+        }
+        writeln!(file, "    }}")?;
+    }
+
+    for &(parent, child) in graph.edges() {
+        if included.contains(&parent) && included.contains(&child) {
+            writeln!(file, "    {} -> {};", parent, child)?;
+        }
+    }
+
+    writeln!(file, "}}")?;
+    Ok(())
+}
+
+/// Collects the set of node IDs reachable from `start` by following edges
+/// forward (parent -> child), matching the "print only what's relevant to
+/// the current node" pattern.
+fn flood_from(graph: &Graph, start: usize) -> HashSet<usize> {
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+    seen.insert(start);
+
+    while let Some(node) = queue.pop_front() {
+        for &child in graph.children(node) {
+            if seen.insert(child) {
+                queue.push_back(child);
+            }
+        }
+    }
+
+    seen
+}
@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::TransactionNode;
+
+/// A nonzero `left`/`right` parent reference that doesn't point at a node
+/// the graph actually has, i.e. `parent > node_count` (`0` means "no
+/// parent" and is never an error).
+#[derive(Debug)]
+pub struct InvalidParentError {
+    pub node: usize,
+    pub parent: usize,
+    pub node_count: usize,
+}
+
+impl fmt::Display for InvalidParentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "node {} references parent {}, which is out of range (1..={})",
+            self.node, self.parent, self.node_count
+        )
+    }
+}
+
+impl std::error::Error for InvalidParentError {}
+
+/// The parent links form a cycle rather than a DAG.
+#[derive(Debug)]
+pub struct CycleError;
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parent links form a cycle")
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// Index-based adjacency view over the transaction DAG, modeled on rustc's
+/// `DepGraphQuery`: nodes are identified by their 1-based position in the
+/// original `TransactionNode` slice, with both forward (parent -> child)
+/// and reverse (child -> parent) adjacency built in a single pass so
+/// metrics like in-references no longer need to rescan the edge list.
+pub struct Graph {
+    node_count: usize,
+    children: Vec<Vec<usize>>,
+    parents: Vec<Vec<usize>>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl Graph {
+    /// Builds the graph from parsed transaction nodes, reserving capacity
+    /// up front from the known node count: `nodes + nodes/4` headroom on
+    /// the per-node adjacency lists and `2*nodes` on the flat edge list,
+    /// since every node has at most two parents. A `left`/`right` of `0` is
+    /// skipped as "no parent"; any other reference outside `1..=node_count`
+    /// is rejected instead of indexing blind.
+    pub fn build(nodes: &[TransactionNode]) -> Result<Self, InvalidParentError> {
+        let node_count = nodes.len();
+        let adjacency_capacity = node_count + node_count / 4;
+        let mut children: Vec<Vec<usize>> = Vec::with_capacity(adjacency_capacity);
+        let mut parents: Vec<Vec<usize>> = Vec::with_capacity(adjacency_capacity);
+        children.resize_with(node_count, Vec::new);
+        parents.resize_with(node_count, Vec::new);
+        let mut edges = Vec::with_capacity(node_count * 2);
+
+        for (node_index, node) in nodes.iter().enumerate() {
+            let current_node = node_index + 1; // Node IDs are 1-based
+
+            for &parent in &[node.left, node.right] {
+                if parent == 0 {
+                    continue;
+                }
+                if parent > node_count {
+                    return Err(InvalidParentError {
+                        node: current_node,
+                        parent,
+                        node_count,
+                    });
+                }
+
+                children[parent - 1].push(current_node);
+                parents[current_node - 1].push(parent);
+                edges.push((parent, current_node));
+            }
+        }
+
+        Ok(Graph {
+            node_count,
+            children,
+            parents,
+            edges,
+        })
+    }
+
+    /// Total number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// All node IDs, 1-based.
+    pub fn nodes(&self) -> impl Iterator<Item = usize> {
+        1..=self.node_count
+    }
+
+    /// All (parent, child) edges.
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+
+    /// Children of `id`, i.e. nodes that reference `id` as a parent.
+    pub fn children(&self, id: usize) -> &[usize] {
+        &self.children[id - 1]
+    }
+
+    /// Parents of `id` (up to two: `left` and `right`).
+    pub fn parents(&self, id: usize) -> &[usize] {
+        &self.parents[id - 1]
+    }
+
+    /// Returns whether `id` is a valid node in this graph (1-based).
+    pub fn contains(&self, id: usize) -> bool {
+        id >= 1 && id <= self.node_count
+    }
+
+    /// Topological order of node IDs by parent-link indegree (Kahn's
+    /// algorithm), parents before children. Returns `Err(CycleError)` if
+    /// the parent links don't form a DAG, instead of looping forever or
+    /// letting a dependent traversal recurse without bound. Shared by every
+    /// metric that needs a cycle-free traversal order, so the cycle check
+    /// only runs once per graph instead of once per metric.
+    pub fn topological_order(&self) -> Result<Vec<usize>, CycleError> {
+        let mut indegree = vec![0usize; self.node_count + 1];
+        for id in self.nodes() {
+            indegree[id] = self.parents(id).len();
+        }
+
+        let mut queue: VecDeque<usize> =
+            (1..=self.node_count).filter(|&id| indegree[id] == 0).collect();
+        let mut order = Vec::with_capacity(self.node_count);
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &child in self.children(node) {
+                indegree[child] -= 1;
+                if indegree[child] == 0 {
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        if order.len() != self.node_count {
+            return Err(CycleError);
+        }
+
+        Ok(order)
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::graph::Graph;
+
+const NO_PARENT: usize = usize::MAX;
+
+/// Binary-lifting ancestor table over a spanning forest of the transaction
+/// DAG, used to answer lowest-common-ancestor and "confirmation confluence"
+/// queries in O(log n) after an O(n log n) build.
+pub struct LowestCommonAncestor {
+    depth: Vec<usize>,
+    parent: Vec<Vec<usize>>,
+    /// `component[v]` is the root of the tree `v` was discovered from; two
+    /// nodes only have a common ancestor if they share one.
+    component: Vec<usize>,
+    log_v: usize,
+}
+
+impl LowestCommonAncestor {
+    /// Builds the ancestor table from a BFS spanning forest: every node not
+    /// yet reached by an earlier tree starts a new one rooted at itself, so
+    /// a tangle with more than one genesis transaction (a perfectly valid
+    /// `left = right = 0` node) still gets every node a tree parent instead
+    /// of being silently left unreachable from a single hardcoded root.
+    pub fn new(graph: &Graph) -> Self {
+        let num_nodes = graph.node_count();
+        let log_v = ((num_nodes as f64).log2().ceil() as usize) + 1;
+        let mut depth = vec![0usize; num_nodes + 1];
+        let mut parent0 = vec![NO_PARENT; num_nodes + 1];
+        let mut component = vec![NO_PARENT; num_nodes + 1];
+
+        let mut visited = HashSet::new();
+        for root in 1..=num_nodes {
+            if !visited.insert(root) {
+                continue;
+            }
+            component[root] = root;
+
+            let mut queue = VecDeque::new();
+            queue.push_back(root);
+            while let Some(node) = queue.pop_front() {
+                for &child in graph.children(node) {
+                    if visited.insert(child) {
+                        depth[child] = depth[node] + 1;
+                        parent0[child] = node;
+                        component[child] = root;
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+
+        let mut parent = vec![vec![NO_PARENT; num_nodes + 1]; log_v];
+        parent[0] = parent0;
+        for k in 1..log_v {
+            for v in 1..=num_nodes {
+                parent[k][v] = if parent[k - 1][v] == NO_PARENT {
+                    NO_PARENT
+                } else {
+                    parent[k - 1][parent[k - 1][v]]
+                };
+            }
+        }
+
+        LowestCommonAncestor {
+            depth,
+            parent,
+            component,
+            log_v,
+        }
+    }
+
+    /// Returns the lowest common ancestor of `u` and `v` in the spanning
+    /// forest, or `None` if they fall in different trees (no path between
+    /// them through the forest, e.g. two different genesis transactions).
+    pub fn lca(&self, u: usize, v: usize) -> Option<usize> {
+        if self.component[u] != self.component[v] {
+            return None;
+        }
+
+        let (mut u, mut v) = (u, v);
+        if self.depth[u] < self.depth[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+
+        let mut diff = self.depth[u] - self.depth[v];
+        let mut k = 0;
+        while diff > 0 {
+            if diff & 1 != 0 {
+                u = self.parent[k][u];
+            }
+            diff >>= 1;
+            k += 1;
+        }
+
+        if u == v {
+            return Some(u);
+        }
+
+        for k in (0..self.log_v).rev() {
+            if self.parent[k][u] != self.parent[k][v] {
+                u = self.parent[k][u];
+                v = self.parent[k][v];
+            }
+        }
+
+        Some(self.parent[0][u])
+    }
+
+    /// Returns the tree distance between `u` and `v`: the number of edges on
+    /// the path between them, routed through their lowest common ancestor.
+    /// `None` if `u` and `v` aren't in the same tree.
+    pub fn distance(&self, u: usize, v: usize) -> Option<usize> {
+        let ancestor = self.lca(u, v)?;
+        Some(self.depth[u] + self.depth[v] - 2 * self.depth[ancestor])
+    }
+
+    /// Batch variant of `lca`, convenient for answering many confluence
+    /// queries without re-deriving the table each time.
+    pub fn batch_lca(&self, pairs: &[(usize, usize)]) -> Vec<Option<usize>> {
+        pairs.iter().map(|&(u, v)| self.lca(u, v)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::TransactionNode;
+
+    fn node(left: usize, right: usize) -> TransactionNode {
+        TransactionNode {
+            left,
+            right,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn diamond_lca_and_distance() {
+        // 1 is genesis; 2 and 3 both reference 1; 4 references 2 and 3.
+        let nodes = vec![node(0, 0), node(1, 0), node(1, 0), node(2, 3)];
+        let graph = Graph::build(&nodes).unwrap();
+        let lca_table = LowestCommonAncestor::new(&graph);
+
+        assert_eq!(lca_table.lca(2, 3), Some(1));
+        assert_eq!(lca_table.distance(2, 3), Some(2));
+        assert_eq!(lca_table.batch_lca(&[(2, 3), (1, 4)]), vec![Some(1), Some(1)]);
+    }
+
+    #[test]
+    fn disconnected_genesis_nodes_have_no_common_ancestor() {
+        // Two independent genesis transactions; node 3 descends from both,
+        // but the spanning forest only gives it one tree parent, so it
+        // shares a component with exactly one of them.
+        let nodes = vec![node(0, 0), node(0, 0), node(1, 2)];
+        let graph = Graph::build(&nodes).unwrap();
+        let lca_table = LowestCommonAncestor::new(&graph);
+
+        assert_eq!(lca_table.lca(2, 3), None);
+        assert_eq!(lca_table.distance(2, 3), None);
+        assert_eq!(lca_table.lca(1, 3), Some(1));
+    }
+}
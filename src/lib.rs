@@ -0,0 +1,17 @@
+pub mod bitset;
+pub mod cumulative_weight;
+pub mod depth;
+pub mod dot;
+pub mod graph;
+pub mod lca;
+pub mod parser;
+
+/// A single ledger entry: the (up to two) parent transactions it approves
+/// and when it was created. `left`/`right` are 1-based node IDs, or `0` for
+/// "no parent" (a genesis transaction).
+#[derive(Debug)]
+pub struct TransactionNode {
+    pub left: usize,
+    pub right: usize,
+    pub timestamp: usize,
+}
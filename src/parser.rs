@@ -0,0 +1,336 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use nom::character::complete::{digit1, space1};
+use nom::combinator::map_res;
+use nom::error::Error as NomError;
+use nom::IResult;
+
+use crate::TransactionNode;
+
+/// A diagnostic produced when a ledger file fails to parse, carrying enough
+/// context to point the user at the offending byte instead of just aborting.
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "parse error at line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The on-disk encoding of a ledger file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    Text,
+    Json,
+    Binary,
+}
+
+impl InputFormat {
+    /// Guesses the format from a file's extension, defaulting to `Text`.
+    pub fn from_extension(path: &str) -> Self {
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("json") => InputFormat::Json,
+            Some("bin") => InputFormat::Binary,
+            _ => InputFormat::Text,
+        }
+    }
+
+    /// Parses the `--format` CLI flag value, if recognized.
+    pub fn from_flag(flag: &str) -> Option<Self> {
+        match flag {
+            "text" => Some(InputFormat::Text),
+            "json" => Some(InputFormat::Json),
+            "binary" => Some(InputFormat::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a ledger file in the given format into its transaction nodes.
+pub fn parse_database(filename: &str, format: InputFormat) -> Result<Vec<TransactionNode>, ParseError> {
+    match format {
+        InputFormat::Text => parse_text(filename),
+        InputFormat::Json => parse_json(filename),
+        InputFormat::Binary => parse_binary(filename),
+    }
+}
+
+// --- Text format: one "<left> <right> <timestamp>" triple per line, after a
+// header line giving the node count (skipped, not checked against the
+// lines that follow). ---
+
+fn parse_triplet(input: &str) -> IResult<&str, (usize, usize, usize)> {
+    let (input, left) = map_res(digit1, str::parse)(input)?;
+    let (input, _) = space1(input)?;
+    let (input, right) = map_res(digit1, str::parse)(input)?;
+    let (input, _) = space1(input)?;
+    let (input, timestamp) = map_res(digit1, str::parse)(input)?;
+    Ok((input, (left, right, timestamp)))
+}
+
+fn parse_text_line(line_number: usize, raw: &str) -> Result<TransactionNode, ParseError> {
+    match parse_triplet(raw) {
+        Ok((remaining, (left, right, timestamp))) if remaining.trim().is_empty() => {
+            Ok(TransactionNode {
+                left,
+                right,
+                timestamp,
+            })
+        }
+        Ok((remaining, _)) => Err(ParseError {
+            line: line_number,
+            column: raw.len() - remaining.len() + 1,
+            message: format!("unexpected trailing input: {:?}", remaining),
+        }),
+        Err(nom::Err::Error(NomError { input: rest, .. }))
+        | Err(nom::Err::Failure(NomError { input: rest, .. })) => Err(ParseError {
+            line: line_number,
+            column: raw.len() - rest.len() + 1,
+            message: "expected \"<left> <right> <timestamp>\"".to_string(),
+        }),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError {
+            line: line_number,
+            column: raw.len() + 1,
+            message: "incomplete line".to_string(),
+        }),
+    }
+}
+
+fn parse_text(filename: &str) -> Result<Vec<TransactionNode>, ParseError> {
+    let contents = fs::read_to_string(filename).map_err(|err| ParseError {
+        line: 0,
+        column: 0,
+        message: format!("failed to open {}: {}", filename, err),
+    })?;
+
+    let mut nodes = Vec::new();
+    for (line_number, line) in contents.lines().enumerate() {
+        if line_number == 0 {
+            continue; // Header: node count, not validated
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let leading = line.len() - line.trim_start().len();
+        let node = parse_text_line(line_number + 1, line.trim()).map_err(|mut err| {
+            err.column += leading;
+            err
+        })?;
+        nodes.push(node);
+    }
+
+    Ok(nodes)
+}
+
+// --- JSON format: an array of {"left": N, "right": N, "timestamp": N}. ---
+
+mod json {
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{char, digit1, multispace0};
+    use nom::combinator::map_res;
+    use nom::multi::separated_list0;
+    use nom::sequence::delimited;
+    use nom::IResult;
+
+    fn ws<'a, F, O>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+    where
+        F: FnMut(&'a str) -> IResult<&'a str, O>,
+    {
+        delimited(multispace0, inner, multispace0)
+    }
+
+    fn number(input: &str) -> IResult<&str, usize> {
+        map_res(digit1, str::parse)(input)
+    }
+
+    fn field<'a>(name: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, usize> {
+        move |input: &'a str| {
+            let (input, _) = ws(tag(name))(input)?;
+            let (input, _) = ws(char(':'))(input)?;
+            number(input)
+        }
+    }
+
+    fn object(input: &str) -> IResult<&str, (usize, usize, usize)> {
+        let (input, _) = ws(char('{'))(input)?;
+        let (input, left) = field("\"left\"")(input)?;
+        let (input, _) = ws(char(','))(input)?;
+        let (input, right) = field("\"right\"")(input)?;
+        let (input, _) = ws(char(','))(input)?;
+        let (input, timestamp) = field("\"timestamp\"")(input)?;
+        let (input, _) = ws(char('}'))(input)?;
+        Ok((input, (left, right, timestamp)))
+    }
+
+    pub fn array(input: &str) -> IResult<&str, Vec<(usize, usize, usize)>> {
+        delimited(
+            ws(char('[')),
+            separated_list0(ws(char(',')), object),
+            ws(char(']')),
+        )(input)
+    }
+}
+
+fn parse_json(filename: &str) -> Result<Vec<TransactionNode>, ParseError> {
+    let contents = fs::read_to_string(filename).map_err(|err| ParseError {
+        line: 0,
+        column: 0,
+        message: format!("failed to open {}: {}", filename, err),
+    })?;
+
+    let leading = contents.len() - contents.trim_start().len();
+    let trimmed = contents.trim();
+    let (remaining, triples) = json::array(trimmed).map_err(|_| ParseError {
+        line: 0,
+        column: 0,
+        message: "expected a JSON array of {left,right,timestamp} objects".to_string(),
+    })?;
+
+    if !remaining.trim().is_empty() {
+        return Err(ParseError {
+            line: 0,
+            column: leading + trimmed.len() - remaining.len() + 1,
+            message: format!("unexpected trailing input: {:?}", remaining),
+        });
+    }
+
+    Ok(triples
+        .into_iter()
+        .map(|(left, right, timestamp)| TransactionNode {
+            left,
+            right,
+            timestamp,
+        })
+        .collect())
+}
+
+// --- Binary format: header u32 node count, then per-node fixed-width
+// little-endian (left: u32, right: u32, timestamp: u64) triples. ---
+
+fn parse_binary(filename: &str) -> Result<Vec<TransactionNode>, ParseError> {
+    let bytes = fs::read(filename).map_err(|err| ParseError {
+        line: 0,
+        column: 0,
+        message: format!("failed to open {}: {}", filename, err),
+    })?;
+
+    if bytes.len() < 4 {
+        return Err(ParseError {
+            line: 0,
+            column: 0,
+            message: "missing node-count header".to_string(),
+        });
+    }
+
+    let node_count = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+    let record_size = 4 + 4 + 8; // left: u32, right: u32, timestamp: u64
+    let expected_len = 4 + node_count * record_size;
+    if bytes.len() < expected_len {
+        return Err(ParseError {
+            line: 0,
+            column: 4,
+            message: format!(
+                "expected {} bytes for {} nodes, found {}",
+                expected_len,
+                node_count,
+                bytes.len()
+            ),
+        });
+    }
+
+    let mut nodes = Vec::with_capacity(node_count);
+    let mut offset = 4;
+    for index in 0..node_count {
+        let left = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+        let right = u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap()) as usize;
+        let timestamp = u64::from_le_bytes(bytes[offset + 8..offset + 16].try_into().unwrap()) as usize;
+
+        nodes.push(TransactionNode {
+            left,
+            right,
+            timestamp,
+        });
+
+        offset += record_size;
+        let _ = index;
+    }
+
+    Ok(nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    fn write_temp(name: &str, contents: impl AsRef<[u8]>) -> String {
+        let path = env::temp_dir().join(format!("ledgerstats_parser_test_{}", name));
+        fs::write(&path, contents).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn text_rejects_malformed_line() {
+        let path = write_temp("malformed_line.txt", "2\n1 2 notanumber\n");
+        let err = parse_database(&path, InputFormat::Text).unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn text_parses_valid_lines() {
+        let path = write_temp("valid.txt", "2\n0 0 100\n1 0 200\n");
+        let nodes = parse_database(&path, InputFormat::Text).unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!((nodes[1].left, nodes[1].right, nodes[1].timestamp), (1, 0, 200));
+    }
+
+    #[test]
+    fn json_rejects_malformed_input() {
+        let path = write_temp("malformed.json", "{ not an array }");
+        let err = parse_database(&path, InputFormat::Json).unwrap_err();
+        assert!(err.message.contains("expected a JSON array"));
+    }
+
+    #[test]
+    fn json_parses_valid_array() {
+        let path = write_temp(
+            "valid.json",
+            r#"[{"left": 0, "right": 0, "timestamp": 1}, {"left": 1, "right": 0, "timestamp": 2}]"#,
+        );
+        let nodes = parse_database(&path, InputFormat::Json).unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!((nodes[1].left, nodes[1].right, nodes[1].timestamp), (1, 0, 2));
+    }
+
+    #[test]
+    fn binary_rejects_missing_header() {
+        let path = write_temp("missing_header.bin", [0u8, 1, 2]);
+        let err = parse_database(&path, InputFormat::Binary).unwrap_err();
+        assert_eq!(err.message, "missing node-count header");
+    }
+
+    #[test]
+    fn binary_rejects_truncated_records() {
+        // Header claims 2 nodes but only enough bytes for a partial first record.
+        let mut bytes = 2u32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[0u8; 4]);
+        let path = write_temp("truncated.bin", bytes);
+        let err = parse_database(&path, InputFormat::Binary).unwrap_err();
+        assert!(err.message.starts_with("expected"));
+    }
+}